@@ -1,14 +1,109 @@
 //! An implementation for [tracing_facade] that emits the [Chromium Trace Event Format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU/preview).
 
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
 use std::ops::DerefMut;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, Weak};
 
 use serde::Serialize;
 
-use tracing_facade::{Event, EventKind};
+use tracing_facade::{Event, EventKind, InstantScope, Tracer as _};
 
-pub struct Tracer {
+/// The amount of serialized event data a thread accumulates locally before merging it into the
+/// shared ring, so that the fast path of [Tracer::ring_buffer] only needs the global lock
+/// occasionally rather than on every event.
+const STAGING_CAPACITY_BYTES: usize = 4096;
+
+thread_local! {
+  // Keyed by the address of the owning [RingBufferSink], since a process may have more than one
+  // ring-buffer [Tracer] and each needs its own per-thread staging buffer.
+  static STAGING_BUFFERS: RefCell<HashMap<usize, Arc<Mutex<StagingBuffer>>>> = RefCell::new(HashMap::new());
+}
+
+enum Sink {
+  /// Writes each event straight to `output` under a lock, as soon as it is recorded.
+  Direct(Mutex<Box<std::io::Write + Send>>),
+
+  /// Accumulates events in a bounded in-memory ring, only writing to `output` on [Tracer::flush]
+  /// or [Drop].
+  RingBuffer(RingBufferSink),
+}
+
+/// A thread's locally accumulated records, staged before being merged into the shared ring.
+///
+/// Records are kept separate, rather than concatenated into one blob, so that [RingBuffer]'s
+/// eviction can drop individual stale records instead of a whole staging buffer's worth at once.
+#[derive(Default)]
+struct StagingBuffer {
+  records: Vec<Vec<u8>>,
+  bytes: usize,
+}
+
+impl StagingBuffer {
+  fn push(&mut self, record: Vec<u8>) {
+    self.bytes += record.len();
+    self.records.push(record);
+  }
+
+  fn take(&mut self) -> Vec<Vec<u8>> {
+    self.bytes = 0;
+    std::mem::take(&mut self.records)
+  }
+}
+
+/// The state backing [Tracer::ring_buffer].
+///
+/// Each thread that records an event stages serialized records into its own buffer. A weak
+/// reference to every staging buffer that has ever been created is kept in `thread_buffers`, so
+/// that [Tracer::flush] can merge every live thread's staged data into `ring`, not just the
+/// flushing thread's; entries belonging to threads that have since exited are pruned as a side
+/// effect of that same merge.
+struct RingBufferSink {
   output: Mutex<Box<std::io::Write + Send>>,
+  ring: Mutex<RingBuffer>,
+  thread_buffers: Mutex<Vec<Weak<Mutex<StagingBuffer>>>>,
+}
+
+impl RingBufferSink {
+  fn staging_buffer(&self) -> Arc<Mutex<StagingBuffer>> {
+    let key = self as *const RingBufferSink as usize;
+    STAGING_BUFFERS.with(|cell| {
+      let mut buffers = cell.borrow_mut();
+      if let Some(buffer) = buffers.get(&key) {
+        return Arc::clone(buffer);
+      }
+
+      let buffer = Arc::new(Mutex::new(StagingBuffer::default()));
+      self.thread_buffers.lock().unwrap().push(Arc::downgrade(&buffer));
+      buffers.insert(key, Arc::clone(&buffer));
+      buffer
+    })
+  }
+
+  /// Merges every live thread's staged records into `ring`, not just the calling thread's, and
+  /// drops the registry entries of threads that have since exited.
+  fn merge_all_staging_buffers(&self) {
+    let mut thread_buffers = self.thread_buffers.lock().unwrap();
+    thread_buffers.retain(|buffer| {
+      let buffer = match buffer.upgrade() {
+        Some(buffer) => buffer,
+        None => return false,
+      };
+
+      let mut staging = buffer.lock().unwrap();
+      if staging.bytes > 0 {
+        let mut ring = self.ring.lock().unwrap();
+        for record in staging.take() {
+          ring.push(record);
+        }
+      }
+      true
+    });
+  }
+}
+
+pub struct Tracer {
+  sink: Sink,
 }
 
 impl tracing_facade::Tracer for Tracer {
@@ -17,12 +112,48 @@ impl tracing_facade::Tracer for Tracer {
   }
 
   fn record_event(&self, event: Event) {
-    let mut lock = self.output.lock().unwrap();
-    write_event(lock.deref_mut(), event);
+    match &self.sink {
+      Sink::Direct(output) => {
+        let mut lock = output.lock().unwrap();
+        write_event(lock.deref_mut(), event);
+      }
+
+      Sink::RingBuffer(sink) => {
+        let staging = sink.staging_buffer();
+        let mut staging = staging.lock().unwrap();
+        let mut record = Vec::new();
+        write_event(&mut record, event);
+        staging.push(record);
+        if staging.bytes >= STAGING_CAPACITY_BYTES {
+          let mut ring = sink.ring.lock().unwrap();
+          for record in staging.take() {
+            ring.push(record);
+          }
+        }
+      }
+    }
   }
 
   fn flush(&self) {
-    let _ = self.output.lock().unwrap().flush();
+    match &self.sink {
+      Sink::Direct(output) => {
+        let _ = output.lock().unwrap().flush();
+      }
+
+      Sink::RingBuffer(sink) => {
+        sink.merge_all_staging_buffers();
+
+        let mut output = sink.output.lock().unwrap();
+        sink.ring.lock().unwrap().drain_into(output.deref_mut());
+        let _ = output.flush();
+      }
+    }
+  }
+}
+
+impl Drop for Tracer {
+  fn drop(&mut self) {
+    self.flush();
   }
 }
 
@@ -30,19 +161,96 @@ impl Tracer {
   pub fn from_output(mut output: Box<std::io::Write + Send>) -> Tracer {
     let _ = output.write_all(b"[");
     Tracer {
-      output: Mutex::new(output),
+      sink: Sink::Direct(Mutex::new(output)),
     }
   }
+
+  /// Constructs a [Tracer] that buffers events in a fixed-capacity in-memory ring, overwriting
+  /// the oldest events once `capacity_bytes` is exceeded, rather than writing every event to
+  /// `output` immediately.
+  ///
+  /// Events are only serialized to `output` when [Tracer::flush] is called or the [Tracer] is
+  /// dropped, making this suitable as an always-on "flight recorder": instrumentation stays
+  /// compiled in and enabled at negligible ongoing cost, and the most recent window of events is
+  /// dumped only when something interesting happens. [Tracer::flush] merges every thread's staged
+  /// events before draining the ring, not just the flushing thread's.
+  pub fn ring_buffer(mut output: Box<std::io::Write + Send>, capacity_bytes: usize) -> Tracer {
+    let _ = output.write_all(b"[");
+    Tracer {
+      sink: Sink::RingBuffer(RingBufferSink {
+        output: Mutex::new(output),
+        ring: Mutex::new(RingBuffer::new(capacity_bytes)),
+        thread_buffers: Mutex::new(Vec::new()),
+      }),
+    }
+  }
+}
+
+/// A fixed-capacity, byte-bounded queue of serialized records.
+///
+/// When pushing a new record would exceed `capacity_bytes`, the oldest records are dropped until
+/// it fits.
+struct RingBuffer {
+  capacity_bytes: usize,
+  used_bytes: usize,
+  records: VecDeque<Vec<u8>>,
+}
+
+impl RingBuffer {
+  fn new(capacity_bytes: usize) -> RingBuffer {
+    RingBuffer {
+      capacity_bytes,
+      used_bytes: 0,
+      records: VecDeque::new(),
+    }
+  }
+
+  fn push(&mut self, record: Vec<u8>) {
+    self.used_bytes += record.len();
+    self.records.push_back(record);
+
+    while self.used_bytes > self.capacity_bytes {
+      match self.records.pop_front() {
+        Some(oldest) => self.used_bytes -= oldest.len(),
+        None => break,
+      }
+    }
+  }
+
+  fn drain_into(&mut self, output: &mut std::io::Write) {
+    for record in self.records.drain(..) {
+      let _ = output.write_all(&record);
+    }
+    self.used_bytes = 0;
+  }
 }
 
 #[derive(Serialize)]
 struct Record<'a> {
   name: &'a str,
+  cat: &'a str,
   ph: &'static str,
   pid: u32,
   tid: u32,
   ts: u64,
   arg: Option<serde_json::Value>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  dur: Option<u64>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  s: Option<char>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  id: Option<u64>,
+}
+
+fn instant_scope_char(scope: InstantScope) -> char {
+  match scope {
+    InstantScope::Thread => 't',
+    InstantScope::Scoped => 's',
+    InstantScope::Global => 'g',
+  }
 }
 
 fn write_event(mut output: &mut std::io::Write, event: Event) {
@@ -50,14 +258,40 @@ fn write_event(mut output: &mut std::io::Write, event: Event) {
   let phase = match event.kind {
     EventKind::SyncBegin => "B",
     EventKind::SyncEnd => "E",
+    EventKind::Instant => "i",
+    EventKind::Counter => "C",
+    EventKind::Complete => "X",
+    EventKind::AsyncBegin => "b",
+    EventKind::AsyncInstant => "n",
+    EventKind::AsyncEnd => "e",
+  };
+
+  // A "Complete" event is recorded once its duration is already known, so `now` is the end of
+  // the interval, not the start; anchor `ts` back to where the interval actually began.
+  let ts = match event.kind {
+    EventKind::Complete => now.saturating_sub(event.duration_us.unwrap_or(0)),
+    _ => now,
   };
 
   let record = Record {
     name: &event.name,
+    cat: &event.category,
     ph: phase,
     pid: std::process::id(),
     tid: gettid::gettid() as u32,
-    ts: now,
+    ts,
+    dur: match event.kind {
+      EventKind::Complete => event.duration_us,
+      _ => None,
+    },
+    s: match event.kind {
+      EventKind::Instant => event.scope.map(instant_scope_char),
+      _ => None,
+    },
+    id: match event.kind {
+      EventKind::AsyncBegin | EventKind::AsyncInstant | EventKind::AsyncEnd => event.id,
+      _ => None,
+    },
     arg: event.metadata.into_json(),
   };
 
@@ -66,3 +300,21 @@ fn write_event(mut output: &mut std::io::Write, event: Event) {
   let _ = serde_json::to_writer(&mut output, &record);
   let _ = output.write_all(b",");
 }
+
+#[cfg(test)]
+mod tests {
+  use super::RingBuffer;
+
+  #[test]
+  fn ring_buffer_evicts_oldest_records_individually() {
+    let mut ring = RingBuffer::new(10);
+    ring.push(vec![0; 6]);
+    ring.push(vec![1; 6]);
+    ring.push(vec![2; 6]);
+
+    // Each push only evicts as many of the oldest *records* as needed to get back under the cap:
+    // two 6-byte records exceed the 10-byte cap, so only the most recent one survives.
+    assert_eq!(ring.records.len(), 1);
+    assert_eq!(ring.records[0], vec![2; 6]);
+  }
+}
@@ -77,7 +77,7 @@
 //!
 //!   assert_eq!(events[1].name, "bar");
 //!   assert_eq!(events[1].kind, tracing_facade::EventKind::SyncBegin);
-//!   assert_eq!(events[1].metadata.as_json(), Some(&json!({"value": 42})));
+//!   assert_eq!(events[1].metadata.as_json(), Some(json!({"value": 42})));
 //!
 //!   assert_eq!(events[2].name, "bar");
 //!   assert_eq!(events[2].kind, tracing_facade::EventKind::SyncEnd);
@@ -90,11 +90,19 @@
 //! ```
 
 use std::borrow::Cow;
+use std::collections::BTreeSet;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
 
 mod macros;
 pub use macros::*;
 
+/// Function attribute macros that wrap a function body to emit events automatically.
+///
+/// See [tracing_facade_macros::instant], [tracing_facade_macros::duration], and
+/// [tracing_facade_macros::counter].
+pub use tracing_facade_macros::{counter, duration, instant};
+
 pub enum Error {}
 
 /// A trait encompassing the operations required for tracing.
@@ -135,6 +143,52 @@ pub enum EventKind {
   /// This represents the end of a duration on a particular thread. Durations can be nested,
   /// but must not overlap.
   SyncEnd,
+
+  /// A single point-in-time event.
+  ///
+  /// Unlike [EventKind::SyncBegin]/[EventKind::SyncEnd], this doesn't need to be paired with
+  /// anything else. [Event::scope] optionally describes how widely the instant should be drawn.
+  Instant,
+
+  /// A sample of one or more named counters.
+  ///
+  /// The numeric fields of [Event::metadata] are interpreted as the values of the counter's
+  /// series at this point in time.
+  Counter,
+
+  /// A duration which is already known in full, rather than being observed via a begin/end pair.
+  ///
+  /// [Event::duration_us] holds the length of the duration, in microseconds.
+  Complete,
+
+  /// The beginning of an asynchronous duration.
+  ///
+  /// Unlike [EventKind::SyncBegin], an asynchronous duration is identified by [Event::id] rather
+  /// than by nesting, so it can be started on one thread and ended on another.
+  AsyncBegin,
+
+  /// A point-in-time event occurring within an asynchronous duration.
+  ///
+  /// Identified by [Event::id], as with [EventKind::AsyncBegin].
+  AsyncInstant,
+
+  /// The end of an asynchronous duration.
+  ///
+  /// Identified by [Event::id], as with [EventKind::AsyncBegin].
+  AsyncEnd,
+}
+
+/// How widely an [EventKind::Instant] event should be drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InstantScope {
+  /// The instant is only relevant to the thread that recorded it.
+  Thread,
+
+  /// The instant is relevant to every thread in the recording process.
+  Scoped,
+
+  /// The instant is relevant to every process being traced.
+  Global,
 }
 
 /// An event to trace.
@@ -145,33 +199,177 @@ pub struct Event<'a> {
   /// The type of [Event] which occurred.
   pub kind: EventKind,
 
+  /// The category this [Event] belongs to.
+  ///
+  /// An empty category is always considered enabled; see [enable_category].
+  pub category: Cow<'a, str>,
+
   /// [Metadata] attached to the event.
   pub metadata: Metadata,
+
+  /// The duration of an [EventKind::Complete] event, in microseconds.
+  ///
+  /// Ignored for every other [EventKind].
+  pub duration_us: Option<u64>,
+
+  /// The scope of an [EventKind::Instant] event.
+  ///
+  /// Ignored for every other [EventKind].
+  pub scope: Option<InstantScope>,
+
+  /// The id identifying an [EventKind::AsyncBegin]/[EventKind::AsyncInstant]/[EventKind::AsyncEnd]
+  /// event.
+  ///
+  /// Events sharing the same name and id are connected into a single asynchronous slice, even if
+  /// they occur on different threads. Ignored for every other [EventKind].
+  pub id: Option<u64>,
+}
+
+impl<'a> Default for Event<'a> {
+  fn default() -> Self {
+    Event {
+      name: Cow::Borrowed(""),
+      kind: EventKind::SyncBegin,
+      category: Cow::Borrowed(""),
+      metadata: Metadata::default(),
+      duration_us: None,
+      scope: None,
+      id: None,
+    }
+  }
+}
+
+/// A typed value for a single named metadata argument.
+///
+/// Prefers one of the scalar variants, which can be recorded without allocating or touching the
+/// `serde_json` machinery; anything else falls back to [ArgumentValue::Json].
+#[derive(Clone, Debug)]
+pub enum ArgumentValue {
+  Bool(bool),
+  I64(i64),
+  U64(u64),
+  F64(f64),
+  Str(Cow<'static, str>),
+  Json(serde_json::Value),
+}
+
+impl ArgumentValue {
+  fn into_json(self) -> serde_json::Value {
+    match self {
+      ArgumentValue::Bool(value) => serde_json::Value::from(value),
+      ArgumentValue::I64(value) => serde_json::Value::from(value),
+      ArgumentValue::U64(value) => serde_json::Value::from(value),
+      ArgumentValue::F64(value) => serde_json::Number::from_f64(value)
+        .map(serde_json::Value::Number)
+        .unwrap_or(serde_json::Value::Null),
+      ArgumentValue::Str(value) => serde_json::Value::from(value.into_owned()),
+      ArgumentValue::Json(value) => value,
+    }
+  }
+}
+
+macro_rules! impl_from_for_argument_value {
+  ($($ty: ty => $variant: ident $(as $cast: ty)*,)+) => {
+    $(
+      impl From<$ty> for ArgumentValue {
+        fn from(value: $ty) -> Self {
+          ArgumentValue::$variant(value $(as $cast)*)
+        }
+      }
+    )+
+  };
+}
+
+impl_from_for_argument_value! {
+  bool => Bool,
+  i32 => I64 as i64,
+  i64 => I64,
+  u32 => U64 as u64,
+  u64 => U64,
+  f32 => F64 as f64,
+  f64 => F64,
+}
+
+impl From<&'static str> for ArgumentValue {
+  fn from(value: &'static str) -> Self {
+    ArgumentValue::Str(Cow::Borrowed(value))
+  }
+}
+
+impl From<String> for ArgumentValue {
+  fn from(value: String) -> Self {
+    ArgumentValue::Str(Cow::Owned(value))
+  }
+}
+
+impl From<serde_json::Value> for ArgumentValue {
+  fn from(value: serde_json::Value) -> Self {
+    ArgumentValue::Json(value)
+  }
+}
+
+#[derive(Clone, Debug)]
+enum MetadataStorage {
+  Empty,
+  Arguments(Vec<(Cow<'static, str>, ArgumentValue)>),
+  Json(serde_json::Value),
 }
 
 /// A struct containing metadata for an event.
+///
+/// Typically built via [Metadata::from_arguments], which stores typed [ArgumentValue] pairs and
+/// only builds a [serde_json::Value] lazily, when a [Tracer] actually asks for one via
+/// [Metadata::as_json]/[Metadata::into_json].
 #[derive(Clone, Debug)]
 pub struct Metadata {
-  json: Option<serde_json::Value>,
+  storage: MetadataStorage,
 }
 
 impl Metadata {
-  pub fn as_json(&self) -> Option<&serde_json::Value> {
-    self.json.as_ref()
+  /// Builds [Metadata] from a set of typed `(name, value)` pairs.
+  pub fn from_arguments(arguments: Vec<(Cow<'static, str>, ArgumentValue)>) -> Metadata {
+    Metadata {
+      storage: MetadataStorage::Arguments(arguments),
+    }
   }
 
-  pub fn into_json(self) -> Option<serde_json::Value> {
-    self.json
+  /// Builds [Metadata] from a pre-built [serde_json::Value].
+  ///
+  /// Kept for compatibility with [Tracer]s and callers that only deal in JSON; prefer
+  /// [Metadata::from_arguments] where possible.
+  pub fn from_json(json: serde_json::Value) -> Metadata {
+    Metadata {
+      storage: MetadataStorage::Json(json),
+    }
   }
 
-  pub fn from_json(json: serde_json::Value) -> Metadata {
-    Metadata { json: Some(json) }
+  pub fn as_json(&self) -> Option<serde_json::Value> {
+    match &self.storage {
+      MetadataStorage::Empty => None,
+      MetadataStorage::Json(json) => Some(json.clone()),
+      MetadataStorage::Arguments(arguments) => Some(arguments_to_json(arguments.iter().cloned())),
+    }
   }
+
+  pub fn into_json(self) -> Option<serde_json::Value> {
+    match self.storage {
+      MetadataStorage::Empty => None,
+      MetadataStorage::Json(json) => Some(json),
+      MetadataStorage::Arguments(arguments) => Some(arguments_to_json(arguments.into_iter())),
+    }
+  }
+}
+
+fn arguments_to_json(arguments: impl Iterator<Item = (Cow<'static, str>, ArgumentValue)>) -> serde_json::Value {
+  let map = arguments.map(|(name, value)| (name.into_owned(), value.into_json())).collect();
+  serde_json::Value::Object(map)
 }
 
 impl Default for Metadata {
   fn default() -> Self {
-    Metadata { json: None }
+    Metadata {
+      storage: MetadataStorage::Empty,
+    }
   }
 }
 
@@ -202,6 +400,31 @@ pub fn supports_metadata() -> bool {
   }
 }
 
+static ENABLED_CATEGORIES: OnceLock<Mutex<BTreeSet<String>>> = OnceLock::new();
+
+fn enabled_categories() -> &'static Mutex<BTreeSet<String>> {
+  ENABLED_CATEGORIES.get_or_init(|| Mutex::new(BTreeSet::new()))
+}
+
+/// Enables recording of [Event]s tagged with the given category.
+///
+/// The empty category is always enabled and is unaffected by this function.
+pub fn enable_category<S: Into<String>>(category: S) {
+  enabled_categories().lock().unwrap().insert(category.into());
+}
+
+/// Disables recording of [Event]s tagged with the given category.
+pub fn disable_category(category: &str) {
+  enabled_categories().lock().unwrap().remove(category);
+}
+
+/// Determines whether [Event]s tagged with the given category should be recorded.
+///
+/// The empty category (the default when no category is specified) is always enabled.
+pub fn is_category_enabled(category: &str) -> bool {
+  category.is_empty() || enabled_categories().lock().unwrap().contains(category)
+}
+
 /// Records an Event to the installed [Tracer].
 ///
 /// If a [Tracer] has been installed, invokes [Tracer::record_event] on it.
@@ -285,3 +508,38 @@ fn set_tracer_impl(tracer: &'static Tracer) {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn category_enablement() {
+    assert!(is_category_enabled(""));
+    assert!(!is_category_enabled("net"));
+
+    enable_category("net");
+    assert!(is_category_enabled("net"));
+    assert!(!is_category_enabled("gpu"));
+
+    disable_category("net");
+    assert!(!is_category_enabled("net"));
+  }
+
+  #[test]
+  fn metadata_typed_arguments_as_json() {
+    let metadata = Metadata::from_arguments(vec![(Cow::Borrowed("count"), ArgumentValue::from(42i64))]);
+    assert_eq!(metadata.as_json(), Some(serde_json::json!({"count": 42})));
+  }
+
+  #[test]
+  fn metadata_json_fallback() {
+    let metadata = Metadata::from_json(serde_json::json!({"values": [1, 2, 3]}));
+    assert_eq!(metadata.as_json(), Some(serde_json::json!({"values": [1, 2, 3]})));
+  }
+
+  #[test]
+  fn metadata_empty_has_no_json() {
+    assert_eq!(Metadata::default().as_json(), None);
+  }
+}
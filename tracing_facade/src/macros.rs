@@ -1,12 +1,58 @@
 #[doc(hidden)]
 pub use scopeguard::guard;
 
+/// Converts a single metadata value into an [ArgumentValue].
+///
+/// Array and object literals are routed to [ArgumentValue::Json], since they have no dedicated
+/// typed representation; everything else goes through [ArgumentValue]'s `From` impls, which cover
+/// the common scalar types without needing to build a [serde_json::Value].
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __tracing_facade_argument_value {
+  ([$($array: tt)*]) => {
+    $crate::ArgumentValue::Json(serde_json::json!([$($array)*]))
+  };
+
+  ({$($object: tt)*}) => {
+    $crate::ArgumentValue::Json(serde_json::json!({$($object)*}))
+  };
+
+  ($value: expr) => {
+    $crate::ArgumentValue::from($value)
+  };
+}
+
+/// Builds [Metadata] from a set of `"name": value` pairs, using the same object syntax accepted by
+/// [serde_json::json!].
+///
+/// Prefers building typed [ArgumentValue] pairs directly via [Metadata::from_arguments], which
+/// avoids touching the `serde_json` machinery on the hot path. Falls back to the old
+/// [Metadata::from_json] behavior for anything the typed path can't parse, e.g. values that aren't
+/// a single token tree.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __tracing_facade_metadata {
+  ($($name: tt : $value: tt),+ $(,)?) => {
+    $crate::Metadata::from_arguments(vec![
+      $(($name.into(), $crate::__tracing_facade_argument_value!($value)),)+
+    ])
+  };
+
+  ($($metadata: tt)+) => {
+    $crate::Metadata::from_json(serde_json::json!({$($metadata)+}))
+  };
+}
+
 /// Records the end of a synchronous duration.
 ///
 /// Accepts an expression of a type that implements [Into<Cow<str>>], with optional metadata
 /// following. Uses of `trace_begin` and `trace_end` must be balanced; in most cases, [trace_scoped]
 /// should be used instead.
 ///
+/// An optional `category: ` argument may be given first to tag the event with a category; events
+/// tagged with a category that hasn't been enabled via [enable_category] are skipped before any
+/// [Metadata] is constructed.
+///
 /// The behavior of Metadata specification depends on the implementation of [Tracer] being used.
 /// Chromium's trace event format will merge metadata from beginning and end, preferring values from
 /// the end in the case of conflict.
@@ -16,26 +62,29 @@ pub use scopeguard::guard;
 /// # #[macro_use] extern crate tracing_facade;
 /// trace_begin!("foo");
 /// trace_begin!("bar", "value": 42);
+/// trace_begin!(category: "net", "io");
 /// trace_end!("bar", "value": 123, "values": [1, 2, 3]);
 /// trace_end!("foo");
+/// trace_end!(category: "net", "io");
 /// ```
 #[macro_export]
 macro_rules! trace_begin {
-  ($name: expr) => {
-    if $crate::is_enabled() {
+  (category: $category: expr, $name: expr) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
       let event = $crate::Event {
         name: $name.into(),
         kind: $crate::EventKind::SyncBegin,
-        metadata: $crate::Metadata::default(),
+        category: $category.into(),
+        ..Default::default()
       };
       $crate::record_event(event);
     }
   };
 
-  ($name: expr, $($metadata: tt)+) => {
-    if $crate::is_enabled() {
+  (category: $category: expr, $name: expr, $($metadata: tt)+) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
       let metadata = if $crate::supports_metadata() {
-        $crate::Metadata::from_json(serde_json::json!({$($metadata)+}))
+        $crate::__tracing_facade_metadata!($($metadata)+)
       } else {
         $crate::Metadata::default()
       };
@@ -43,11 +92,21 @@ macro_rules! trace_begin {
       let event = $crate::Event {
         name: $name.into(),
         kind: $crate::EventKind::SyncBegin,
+        category: $category.into(),
         metadata,
+        ..Default::default()
       };
       $crate::record_event(event);
     }
   };
+
+  ($name: expr) => {
+    $crate::trace_begin!(category: "", $name);
+  };
+
+  ($name: expr, $($metadata: tt)+) => {
+    $crate::trace_begin!(category: "", $name, $($metadata)+);
+  };
 }
 
 /// Records the end of a synchronous duration.
@@ -56,6 +115,10 @@ macro_rules! trace_begin {
 /// following. Uses of `trace_begin` and `trace_end` must be balanced; in most cases, [trace_scoped]
 /// should be used instead.
 ///
+/// An optional `category: ` argument may be given first to tag the event with a category; events
+/// tagged with a category that hasn't been enabled via [enable_category] are skipped before any
+/// [Metadata] is constructed.
+///
 /// The behavior of Metadata specification depends on the implementation of [Tracer] being used.
 /// Chromium's trace event format will merge metadata from beginning and end, preferring values from
 /// the end in the case of conflict.
@@ -70,21 +133,22 @@ macro_rules! trace_begin {
 /// ```
 #[macro_export]
 macro_rules! trace_end {
-  ($name: expr) => {
-    if $crate::is_enabled() {
+  (category: $category: expr, $name: expr) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
       let event = $crate::Event {
         name: $name.into(),
         kind: $crate::EventKind::SyncEnd,
-        metadata: $crate::Metadata::default(),
+        category: $category.into(),
+        ..Default::default()
       };
       $crate::record_event(event);
     }
   };
 
-  ($name: expr, $($metadata: tt)+) => {
-    if $crate::is_enabled() {
+  (category: $category: expr, $name: expr, $($metadata: tt)+) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
       let metadata = if $crate::supports_metadata() {
-        $crate::Metadata::from_json(serde_json::json!({$($metadata)+}))
+        $crate::__tracing_facade_metadata!($($metadata)+)
       } else {
         $crate::Metadata::default()
       };
@@ -92,45 +156,406 @@ macro_rules! trace_end {
       let event = $crate::Event {
         name: $name.into(),
         kind: $crate::EventKind::SyncEnd,
+        category: $category.into(),
         metadata,
+        ..Default::default()
       };
       $crate::record_event(event);
     }
   };
+
+  ($name: expr) => {
+    $crate::trace_end!(category: "", $name);
+  };
+
+  ($name: expr, $($metadata: tt)+) => {
+    $crate::trace_end!(category: "", $name, $($metadata)+);
+  };
+}
+
+/// Records a single point-in-time event.
+///
+/// Accepts an expression of a type that implements [Into<Cow<str>>], with an optional
+/// [InstantScope] and optional metadata following. If no scope is specified, [InstantScope::Thread]
+/// is assumed. As with [trace_begin]/[trace_end], an optional `category: ` argument may be given
+/// first.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate tracing_facade;
+/// trace_instant!("foo");
+/// trace_instant!("bar", tracing_facade::InstantScope::Global);
+/// trace_instant!("baz", tracing_facade::InstantScope::Global, "value": 42);
+/// trace_instant!(category: "net", "io", tracing_facade::InstantScope::Global);
+/// ```
+#[macro_export]
+macro_rules! trace_instant {
+  (category: $category: expr, $name: expr) => {
+    $crate::trace_instant!(category: $category, $name, $crate::InstantScope::Thread);
+  };
+
+  (category: $category: expr, $name: expr, $scope: expr) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
+      let event = $crate::Event {
+        name: $name.into(),
+        kind: $crate::EventKind::Instant,
+        category: $category.into(),
+        scope: Some($scope),
+        ..Default::default()
+      };
+      $crate::record_event(event);
+    }
+  };
+
+  (category: $category: expr, $name: expr, $scope: expr, $($metadata: tt)+) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
+      let metadata = if $crate::supports_metadata() {
+        $crate::__tracing_facade_metadata!($($metadata)+)
+      } else {
+        $crate::Metadata::default()
+      };
+
+      let event = $crate::Event {
+        name: $name.into(),
+        kind: $crate::EventKind::Instant,
+        category: $category.into(),
+        metadata,
+        scope: Some($scope),
+        ..Default::default()
+      };
+      $crate::record_event(event);
+    }
+  };
+
+  ($name: expr) => {
+    $crate::trace_instant!(category: "", $name, $crate::InstantScope::Thread);
+  };
+
+  ($name: expr, $scope: expr) => {
+    $crate::trace_instant!(category: "", $name, $scope);
+  };
+
+  ($name: expr, $scope: expr, $($metadata: tt)+) => {
+    $crate::trace_instant!(category: "", $name, $scope, $($metadata)+);
+  };
+}
+
+/// Records a sample of one or more named counters.
+///
+/// Accepts an expression of a type that implements [Into<Cow<str>>], followed by metadata whose
+/// numeric fields are interpreted as the values of the counter's series at this point in time. As
+/// with [trace_begin]/[trace_end], an optional `category: ` argument may be given first.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate tracing_facade;
+/// trace_counter!("heap", "used_bytes": 4096, "free_bytes": 1024);
+/// trace_counter!(category: "mem", "heap", "used_bytes": 4096);
+/// ```
+#[macro_export]
+macro_rules! trace_counter {
+  (category: $category: expr, $name: expr, $($metadata: tt)+) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
+      let metadata = if $crate::supports_metadata() {
+        $crate::__tracing_facade_metadata!($($metadata)+)
+      } else {
+        $crate::Metadata::default()
+      };
+
+      let event = $crate::Event {
+        name: $name.into(),
+        kind: $crate::EventKind::Counter,
+        category: $category.into(),
+        metadata,
+        ..Default::default()
+      };
+      $crate::record_event(event);
+    }
+  };
+
+  ($name: expr, $($metadata: tt)+) => {
+    $crate::trace_counter!(category: "", $name, $($metadata)+);
+  };
+}
+
+/// Records a duration which is already known in full.
+///
+/// Accepts an expression of a type that implements [Into<Cow<str>>], followed by the duration of
+/// the event in microseconds, with optional metadata following. Unlike [trace_begin]/[trace_end],
+/// a single `trace_complete!` call is enough to describe the whole duration. As with
+/// [trace_begin]/[trace_end], an optional `category: ` argument may be given first.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate tracing_facade;
+/// trace_complete!("decode_frame", 1250);
+/// trace_complete!("decode_frame", 1250, "frame_number": 42);
+/// trace_complete!(category: "decode", "decode_frame", 1250);
+/// ```
+#[macro_export]
+macro_rules! trace_complete {
+  (category: $category: expr, $name: expr, $duration_us: expr) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
+      let event = $crate::Event {
+        name: $name.into(),
+        kind: $crate::EventKind::Complete,
+        category: $category.into(),
+        duration_us: Some($duration_us),
+        ..Default::default()
+      };
+      $crate::record_event(event);
+    }
+  };
+
+  (category: $category: expr, $name: expr, $duration_us: expr, $($metadata: tt)+) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
+      let metadata = if $crate::supports_metadata() {
+        $crate::__tracing_facade_metadata!($($metadata)+)
+      } else {
+        $crate::Metadata::default()
+      };
+
+      let event = $crate::Event {
+        name: $name.into(),
+        kind: $crate::EventKind::Complete,
+        category: $category.into(),
+        metadata,
+        duration_us: Some($duration_us),
+        ..Default::default()
+      };
+      $crate::record_event(event);
+    }
+  };
+
+  ($name: expr, $duration_us: expr) => {
+    $crate::trace_complete!(category: "", $name, $duration_us);
+  };
+
+  ($name: expr, $duration_us: expr, $($metadata: tt)+) => {
+    $crate::trace_complete!(category: "", $name, $duration_us, $($metadata)+);
+  };
+}
+
+/// Records the beginning of an asynchronous duration.
+///
+/// Accepts an expression of a type that implements [Into<Cow<str>>], followed by a `u64` id, with
+/// optional metadata following. Unlike [trace_begin], the matching [trace_async_end] does not need
+/// to occur on the same thread; events are connected by sharing the same name and id. As with
+/// [trace_begin]/[trace_end], an optional `category: ` argument may be given first.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate tracing_facade;
+/// trace_async_begin!("fetch", 1);
+/// trace_async_begin!("fetch", 1, "url": "https://example.com");
+/// trace_async_end!("fetch", 1);
+/// ```
+#[macro_export]
+macro_rules! trace_async_begin {
+  (category: $category: expr, $name: expr, $id: expr) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
+      let event = $crate::Event {
+        name: $name.into(),
+        kind: $crate::EventKind::AsyncBegin,
+        category: $category.into(),
+        id: Some($id),
+        ..Default::default()
+      };
+      $crate::record_event(event);
+    }
+  };
+
+  (category: $category: expr, $name: expr, $id: expr, $($metadata: tt)+) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
+      let metadata = if $crate::supports_metadata() {
+        $crate::__tracing_facade_metadata!($($metadata)+)
+      } else {
+        $crate::Metadata::default()
+      };
+
+      let event = $crate::Event {
+        name: $name.into(),
+        kind: $crate::EventKind::AsyncBegin,
+        category: $category.into(),
+        metadata,
+        id: Some($id),
+        ..Default::default()
+      };
+      $crate::record_event(event);
+    }
+  };
+
+  ($name: expr, $id: expr) => {
+    $crate::trace_async_begin!(category: "", $name, $id);
+  };
+
+  ($name: expr, $id: expr, $($metadata: tt)+) => {
+    $crate::trace_async_begin!(category: "", $name, $id, $($metadata)+);
+  };
+}
+
+/// Records a point-in-time event occurring within an asynchronous duration.
+///
+/// Accepts an expression of a type that implements [Into<Cow<str>>], followed by the `u64` id
+/// shared with the [trace_async_begin]/[trace_async_end] pair it belongs to, with optional
+/// metadata following. As with [trace_begin]/[trace_end], an optional `category: ` argument may
+/// be given first.
+#[macro_export]
+macro_rules! trace_async_instant {
+  (category: $category: expr, $name: expr, $id: expr) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
+      let event = $crate::Event {
+        name: $name.into(),
+        kind: $crate::EventKind::AsyncInstant,
+        category: $category.into(),
+        id: Some($id),
+        ..Default::default()
+      };
+      $crate::record_event(event);
+    }
+  };
+
+  (category: $category: expr, $name: expr, $id: expr, $($metadata: tt)+) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
+      let metadata = if $crate::supports_metadata() {
+        $crate::__tracing_facade_metadata!($($metadata)+)
+      } else {
+        $crate::Metadata::default()
+      };
+
+      let event = $crate::Event {
+        name: $name.into(),
+        kind: $crate::EventKind::AsyncInstant,
+        category: $category.into(),
+        metadata,
+        id: Some($id),
+        ..Default::default()
+      };
+      $crate::record_event(event);
+    }
+  };
+
+  ($name: expr, $id: expr) => {
+    $crate::trace_async_instant!(category: "", $name, $id);
+  };
+
+  ($name: expr, $id: expr, $($metadata: tt)+) => {
+    $crate::trace_async_instant!(category: "", $name, $id, $($metadata)+);
+  };
+}
+
+/// Records the end of an asynchronous duration.
+///
+/// Accepts an expression of a type that implements [Into<Cow<str>>], followed by the `u64` id
+/// shared with the [trace_async_begin] that started the duration, with optional metadata
+/// following. As with [trace_begin]/[trace_end], an optional `category: ` argument may be given
+/// first.
+#[macro_export]
+macro_rules! trace_async_end {
+  (category: $category: expr, $name: expr, $id: expr) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
+      let event = $crate::Event {
+        name: $name.into(),
+        kind: $crate::EventKind::AsyncEnd,
+        category: $category.into(),
+        id: Some($id),
+        ..Default::default()
+      };
+      $crate::record_event(event);
+    }
+  };
+
+  (category: $category: expr, $name: expr, $id: expr, $($metadata: tt)+) => {
+    if $crate::is_enabled() && $crate::is_category_enabled($category) {
+      let metadata = if $crate::supports_metadata() {
+        $crate::__tracing_facade_metadata!($($metadata)+)
+      } else {
+        $crate::Metadata::default()
+      };
+
+      let event = $crate::Event {
+        name: $name.into(),
+        kind: $crate::EventKind::AsyncEnd,
+        category: $category.into(),
+        metadata,
+        id: Some($id),
+        ..Default::default()
+      };
+      $crate::record_event(event);
+    }
+  };
+
+  ($name: expr, $id: expr) => {
+    $crate::trace_async_end!(category: "", $name, $id);
+  };
+
+  ($name: expr, $id: expr, $($metadata: tt)+) => {
+    $crate::trace_async_end!(category: "", $name, $id, $($metadata)+);
+  };
 }
 
 /// Traces in a given scope.
 ///
 /// [trace_scoped] calls [trace_begin], and then constructs a scope guard that calls [trace_end]
-/// upon the exit of the scope. Metadata, if specified, is provided to only [trace_begin].
+/// upon the exit of the scope. Metadata, if specified, is provided to only [trace_begin]. As with
+/// [trace_begin]/[trace_end], an optional `category: ` argument may be given first.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate tracing_facade;
+/// trace_scoped!("foo");
+/// trace_scoped!("bar", "value": 42);
+/// trace_scoped!(category: "net", "io");
+/// ```
 #[macro_export]
 macro_rules! trace_scoped {
-  ($name: expr) => {
-    let guard = if $crate::is_enabled() {
+  (category: $category: expr, $name: expr) => {
+    let guard = if $crate::is_enabled() && $crate::is_category_enabled($category) {
       let name: std::borrow::Cow<str> = $name.into();
-      $crate::trace_begin!(name.clone());
+      let event = $crate::Event {
+        name: name.clone(),
+        kind: $crate::EventKind::SyncBegin,
+        category: $category.into(),
+        ..Default::default()
+      };
+      $crate::record_event(event);
       Some($crate::guard(name, move |name| {
-        $crate::trace_end!(name);
+        $crate::trace_end!(category: $category, name);
       }))
     } else {
       None
     };
   };
 
-  ($name: expr, $($metadata: tt)+) => {
-    let guard = if $crate::is_enabled() {
+  (category: $category: expr, $name: expr, $($metadata: tt)+) => {
+    let guard = if $crate::is_enabled() && $crate::is_category_enabled($category) {
       let name: std::borrow::Cow<str> = $name.into();
       let metadata = if $crate::supports_metadata() {
-        $crate::Metadata::from_json(serde_json::json!({$($metadata)+}))
+        $crate::__tracing_facade_metadata!($($metadata)+)
       } else {
         $crate::Metadata::default()
       };
-      $crate::trace_begin!(name.clone(), metadata);
+      let event = $crate::Event {
+        name: name.clone(),
+        kind: $crate::EventKind::SyncBegin,
+        category: $category.into(),
+        metadata,
+        ..Default::default()
+      };
+      $crate::record_event(event);
       Some($crate::guard(name, move |name| {
-        $crate::trace_end!(name);
+        $crate::trace_end!(category: $category, name);
       }))
     } else {
       None
     };
   };
+
+  ($name: expr) => {
+    $crate::trace_scoped!(category: "", $name);
+  };
+
+  ($name: expr, $($metadata: tt)+) => {
+    $crate::trace_scoped!(category: "", $name, $($metadata)+);
+  };
 }
@@ -0,0 +1,87 @@
+//! Procedural attribute macros for function-level instrumentation with [tracing_facade].
+//!
+//! These wrap a function body so that entering it automatically emits the appropriate event,
+//! using the function's name as the event name, removing the boilerplate of manually placing
+//! `trace_*!` calls at the top of every instrumented function.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, AttributeArgs, Ident, ItemFn, Lit, Meta, NestedMeta};
+
+/// Extracts a `category = "..."` argument, if one was given.
+fn parse_category(args: AttributeArgs) -> String {
+  for arg in args {
+    if let NestedMeta::Meta(Meta::NameValue(name_value)) = arg {
+      if name_value.path.is_ident("category") {
+        if let Lit::Str(category) = name_value.lit {
+          return category.value();
+        }
+      }
+    }
+  }
+  String::new()
+}
+
+/// Emits a [trace_instant] event, named after the function, on entry.
+///
+/// Accepts an optional `category` argument, e.g. `#[instant(category = "net")]`.
+#[proc_macro_attribute]
+pub fn instant(args: TokenStream, input: TokenStream) -> TokenStream {
+  let category = parse_category(parse_macro_input!(args as AttributeArgs));
+  let mut func = parse_macro_input!(input as ItemFn);
+  let name = func.sig.ident.to_string();
+  let block = func.block;
+
+  func.block = Box::new(syn::parse_quote! {{
+    tracing_facade::trace_instant!(category: #category, #name);
+    #block
+  }});
+
+  TokenStream::from(quote!(#func))
+}
+
+/// Wraps the function body in a [trace_scoped] duration, named after the function.
+///
+/// Accepts an optional `category` argument, e.g. `#[duration(category = "net")]`.
+#[proc_macro_attribute]
+pub fn duration(args: TokenStream, input: TokenStream) -> TokenStream {
+  let category = parse_category(parse_macro_input!(args as AttributeArgs));
+  let mut func = parse_macro_input!(input as ItemFn);
+  let name = func.sig.ident.to_string();
+  let block = func.block;
+
+  func.block = Box::new(syn::parse_quote! {{
+    tracing_facade::trace_scoped!(category: #category, #name);
+    #block
+  }});
+
+  TokenStream::from(quote!(#func))
+}
+
+/// Increments and emits a per-function invocation counter each time the function is entered while
+/// tracing and the function's category are enabled; calls made while either is disabled are not
+/// counted.
+///
+/// Accepts an optional `category` argument, e.g. `#[counter(category = "net")]`.
+#[proc_macro_attribute]
+pub fn counter(args: TokenStream, input: TokenStream) -> TokenStream {
+  let category = parse_category(parse_macro_input!(args as AttributeArgs));
+  let mut func = parse_macro_input!(input as ItemFn);
+  let name = func.sig.ident.to_string();
+  let counter_ident = Ident::new(&format!("__TRACING_FACADE_COUNTER_{}", name.to_uppercase()), Span::call_site());
+  let block = func.block;
+
+  func.block = Box::new(syn::parse_quote! {{
+    static #counter_ident: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    if tracing_facade::is_enabled() && tracing_facade::is_category_enabled(#category) {
+      let count = #counter_ident.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+      tracing_facade::trace_counter!(category: #category, #name, "count": count);
+    }
+    #block
+  }});
+
+  TokenStream::from(quote!(#func))
+}